@@ -7,11 +7,13 @@
 use core::result::Result::Ok;
 
 use kernel::{
-    bit, define_of_id_table, device, gpio,
+    bit, define_of_id_table, delay, device, gpio, irq, pinctrl, seq_print,
     io_mem::IoMem,
     module_platform_driver, of, platform,
     prelude::*,
-    sync::{Arc, ArcBorrow},
+    seq_file::SeqFile,
+    str::{CStr, CString},
+    sync::{Arc, ArcBorrow, SpinLock},
 };
 
 macro_rules! FSEL_REG {
@@ -43,9 +45,35 @@ const GPFSEL0: usize = 0x0; //function select
 const GPSET0: usize = 0x1c; //pin output set
 const GPCLR0: usize = 0x28; //pin output clear
 const GPLEV0: usize = 0x34; //pin level
+const GPEDS0: usize = 0x40; //event detect status
+const GPREN0: usize = 0x4c; //rising edge detect enable
+const GPFEN0: usize = 0x58; //falling edge detect enable
+const GPHEN0: usize = 0x64; //high level detect enable
+const GPLEN0: usize = 0x70; //low level detect enable
+const GPAREN0: usize = 0x7c; //async rising edge detect enable
+const GPAFEN0: usize = 0x88; //async falling edge detect enable
+const GPPUD: usize = 0x94; //pull-up/down enable
+const GPPUDCLK0: usize = 0x98; //pull-up/down enable clock
+const GPIO_PUP_PDN_CNTRL_REG0: usize = 0xe4; //BCM2711 pull select, 2 bits/pin, 16 pins/reg
 const GPIO_SIZE: usize = 0x1000;
 
+// Generic pull value as passed to {bcm2835,bcm2711}_pull_set(); the two
+// variants encode this differently in hardware.
+const BCM2835_PUD_OFF: u32 = 0;
+const BCM2835_PUD_DOWN: u32 = 1;
+const BCM2835_PUD_UP: u32 = 2;
+
+// Subset of the generic PIN_CONFIG_* enum (include/linux/pinctrl/pinconf-generic.h)
+// that this driver can actually act on.
+const PIN_CONFIG_BIAS_DISABLE: u32 = 1;
+const PIN_CONFIG_BIAS_PULL_UP: u32 = 5;
+const PIN_CONFIG_BIAS_PULL_DOWN: u32 = 3;
+
 const BCM2835_NUM_GPIOS: u16 = 54;
+const BCM2711_NUM_GPIOS: u16 = 58;
+// Upper bound used to size the fixed per-pin arrays below; actual pin count
+// for a given instance comes from its matched IdInfo.
+const MAX_GPIOS: usize = BCM2711_NUM_GPIOS as usize;
 
 // bcm2835_fsel
 const BCM2835_FSEL_MASK: u32 = 0x7;
@@ -53,20 +81,91 @@ const BCM2835_FSEL_MASK: u32 = 0x7;
 const BCM2835_FSEL_GPIO_IN: u32 = 0;
 const BCM2835_FSEL_GPIO_OUT: u32 = 1;
 
-
+// BCM2835 doesn't encode alt functions linearly after GPIO_OUT; alt0..alt5
+// map onto these FSEL values (see bcm2835_pinctrl_set_mux()).
+const BCM2835_FSEL_ALT_MAP: [u32; 6] = [4, 5, 6, 7, 3, 2];
+const BCM2835_FSEL_ALT_NAMES: [&str; 6] = ["alt0", "alt1", "alt2", "alt3", "alt4", "alt5"];
+
+// Mirrors the IRQ_TYPE_* flags from include/linux/irq.h; this driver only
+// ever sees them via the gpio irqchip facade, so they are restated here
+// rather than pulled in through a wider dependency.
+const IRQ_TYPE_EDGE_RISING: u32 = 0x1;
+const IRQ_TYPE_EDGE_FALLING: u32 = 0x2;
+const IRQ_TYPE_EDGE_BOTH: u32 = IRQ_TYPE_EDGE_RISING | IRQ_TYPE_EDGE_FALLING;
+const IRQ_TYPE_LEVEL_HIGH: u32 = 0x4;
+const IRQ_TYPE_LEVEL_LOW: u32 = 0x8;
+
+// Per-pin trigger bookkeeping: which of the enable registers below is
+// currently armed for a given hwirq, so mask/unmask only touch the
+// register(s) that set_type actually configured.
+const TRIGGER_RISING: u8 = 1 << 0;
+const TRIGGER_FALLING: u8 = 1 << 1;
+const TRIGGER_HIGH: u8 = 1 << 2;
+const TRIGGER_LOW: u8 = 1 << 3;
+// The async variants latch an edge independently of the core clock, so
+// edge types arm them alongside the synchronous enable to avoid missing
+// an edge while the GPIO block's clock is gated (e.g. in low-power states).
+const TRIGGER_ASYNC_RISING: u8 = 1 << 4;
+const TRIGGER_ASYNC_FALLING: u8 = 1 << 5;
+
+const TRIGGER_REGS: [(u8, usize); 6] = [
+    (TRIGGER_RISING, GPREN0),
+    (TRIGGER_FALLING, GPFEN0),
+    (TRIGGER_HIGH, GPHEN0),
+    (TRIGGER_LOW, GPLEN0),
+    (TRIGGER_ASYNC_RISING, GPAREN0),
+    (TRIGGER_ASYNC_FALLING, GPAFEN0),
+];
+
+// One bank per 32 GPIOs; BCM2835 has two.
+const BCM2835_NUM_BANKS: usize = 2;
 
 struct BCM2835Resources {
     base: IoMem<GPIO_SIZE>,
 }
 
+// Per-compatible data selected at match time: GPIO count and which pull
+// register layout the SoC uses (BCM2711 dropped the GPPUD handshake).
+#[derive(Clone, Copy)]
+enum PullVariant {
+    Bcm2835,
+    Bcm2711,
+}
+
+#[derive(Clone, Copy)]
+struct BCM2835IdInfo {
+    num_gpios: u16,
+    pull_variant: PullVariant,
+}
+
 struct BCM2835Data {
     dev: device::Device,
+    num_gpios: u16,
+    pull_variant: PullVariant,
+    // Serializes read-modify-write access to the edge/level enable
+    // registers (shared across banks and CPUs) and records, per pin,
+    // which trigger register(s) set_type armed for it.
+    irq_lock: SpinLock<[u8; MAX_GPIOS]>,
+    // One slot per pin, holding the requester's label once claimed via
+    // gpio::Chip::request(); None means the pin is free.
+    labels: SpinLock<[Option<CString>; MAX_GPIOS]>,
+    // Last pull bias programmed via pin_config_set(), for debugfs reporting;
+    // the BCM2835's GPPUD register is write-only so this can't be read back.
+    pull_state: SpinLock<[Option<u32>; MAX_GPIOS]>,
 }
 
 type BCM2835Registrations = gpio::Registration<BCM2835Device>;
 
 type DeviceData = device::Data<BCM2835Registrations, BCM2835Resources, BCM2835Data>;
 
+// What a pin's FSEL field currently selects; unlike the raw FSEL code this
+// distinguishes the individual alt functions instead of collapsing them.
+enum PinFunction {
+    Input,
+    Output,
+    Alt(usize),
+}
+
 struct BCM2835Device;
 
 impl BCM2835Device {
@@ -130,27 +229,180 @@ impl BCM2835Device {
         Self::bcm2835_gpio_wr(data, FSEL_REG!(pin), val)?;
         Ok(())
     }
+
+    #[inline]
+    fn bcm2835_gpio_irq_rmw(
+        data: ArcBorrow<'_, DeviceData>,
+        reg: usize,
+        offset: u32,
+        set: bool,
+    ) -> Result {
+        let bank_reg = reg + GPIO_REG_OFFSET!(offset as usize) * 4;
+        let mut val = Self::bcm2835_gpio_rd(data, bank_reg)?;
+        let mask: u32 = bit(GPIO_REG_SHIFT!(offset)).into();
+
+        if set {
+            val |= mask;
+        } else {
+            val &= !mask;
+        }
+
+        Self::bcm2835_gpio_wr(data, bank_reg, val)
+    }
+
+    fn bcm2835_irq_set_type(data: ArcBorrow<'_, DeviceData>, hwirq: u32, irq_type: u32) -> Result {
+        let triggers = match irq_type {
+            IRQ_TYPE_EDGE_RISING => TRIGGER_RISING | TRIGGER_ASYNC_RISING,
+            IRQ_TYPE_EDGE_FALLING => TRIGGER_FALLING | TRIGGER_ASYNC_FALLING,
+            IRQ_TYPE_EDGE_BOTH => {
+                TRIGGER_RISING | TRIGGER_FALLING | TRIGGER_ASYNC_RISING | TRIGGER_ASYNC_FALLING
+            }
+            IRQ_TYPE_LEVEL_HIGH => TRIGGER_HIGH,
+            IRQ_TYPE_LEVEL_LOW => TRIGGER_LOW,
+            _ => return Err(EINVAL),
+        };
+
+        let mut state = data.irq_lock.lock();
+        for &(flag, reg) in TRIGGER_REGS.iter() {
+            Self::bcm2835_gpio_irq_rmw(data, reg, hwirq, triggers & flag != 0)?;
+        }
+        state[hwirq as usize] = triggers;
+
+        Ok(())
+    }
+
+    fn bcm2835_irq_mask(data: ArcBorrow<'_, DeviceData>, hwirq: u32) -> Result {
+        let state = data.irq_lock.lock();
+        let triggers = state[hwirq as usize];
+
+        for &(flag, reg) in TRIGGER_REGS.iter() {
+            if triggers & flag != 0 {
+                Self::bcm2835_gpio_irq_rmw(data, reg, hwirq, false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bcm2835_irq_unmask(data: ArcBorrow<'_, DeviceData>, hwirq: u32) -> Result {
+        let state = data.irq_lock.lock();
+        let triggers = state[hwirq as usize];
+
+        for &(flag, reg) in TRIGGER_REGS.iter() {
+            if triggers & flag != 0 {
+                Self::bcm2835_gpio_irq_rmw(data, reg, hwirq, true)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bcm2835_pinctrl_get_function(
+        data: ArcBorrow<'_, DeviceData>,
+        pin: usize,
+    ) -> Result<PinFunction> {
+        let fsel = Self::bcm2835_pinctrl_fsel_get(data, pin)?;
+
+        Ok(match fsel {
+            BCM2835_FSEL_GPIO_IN => PinFunction::Input,
+            BCM2835_FSEL_GPIO_OUT => PinFunction::Output,
+            other => {
+                let altfn = BCM2835_FSEL_ALT_MAP
+                    .iter()
+                    .position(|&f| f == other)
+                    .ok_or(EINVAL)?;
+                PinFunction::Alt(altfn)
+            }
+        })
+    }
+
+    fn bcm2835_pinctrl_set_mux(data: ArcBorrow<'_, DeviceData>, pin: usize, altfn: usize) -> Result {
+        let fsel = *BCM2835_FSEL_ALT_MAP.get(altfn).ok_or(EINVAL)?;
+        Self::bcm2835_pinctrl_fsel_set(data, pin, fsel)
+    }
+
+    // The pull control signal needs ~150 clock cycles to latch before it is
+    // safe to clock it in via GPPUDCLK; there is no cycle-accurate primitive
+    // exposed to this driver, so approximate with the smallest delay available.
+    #[inline]
+    fn bcm2835_pull_delay() {
+        delay::udelay(1);
+    }
+
+    fn bcm2835_pull_set(data: ArcBorrow<'_, DeviceData>, pin: usize, pull: u32) -> Result {
+        let clk_reg = GPPUDCLK0 + GPIO_REG_OFFSET!(pin) * 4;
+        let clk_bit: u32 = bit(GPIO_REG_SHIFT!(pin as u32)).into();
+
+        Self::bcm2835_gpio_wr(data, GPPUD, pull)?;
+        Self::bcm2835_pull_delay();
+
+        Self::bcm2835_gpio_wr(data, clk_reg, clk_bit)?;
+        Self::bcm2835_pull_delay();
+
+        Self::bcm2835_gpio_wr(data, GPPUD, BCM2835_PUD_OFF)?;
+        Self::bcm2835_gpio_wr(data, clk_reg, 0)?;
+
+        Ok(())
+    }
+
+    fn bcm2711_pull_set(data: ArcBorrow<'_, DeviceData>, pin: usize, pull: u32) -> Result {
+        //BCM2711 encoding is inverted relative to BCM2835: 0=none, 1=up, 2=down.
+        let hw_pull = match pull {
+            BCM2835_PUD_OFF => 0,
+            BCM2835_PUD_UP => 1,
+            BCM2835_PUD_DOWN => 2,
+            _ => return Err(EINVAL),
+        };
+
+        let reg = GPIO_PUP_PDN_CNTRL_REG0 + (pin / 16) * 4;
+        let shift = (pin % 16) * 2;
+
+        let mut val = Self::bcm2835_gpio_rd(data, reg)?;
+        val &= !(0x3 << shift);
+        val |= hw_pull << shift;
+        Self::bcm2835_gpio_wr(data, reg, val)
+    }
+
+    // Demuxes one bank's GPEDS register: for every pending pin, acknowledges
+    // by writing the bit back, then dispatches its mapped Linux IRQ.
+    fn bcm2835_gpio_irq_bank_handler(data: ArcBorrow<'_, DeviceData>, bank: usize) -> Result {
+        let eds_reg = GPEDS0 + bank * 4;
+        let pending = Self::bcm2835_gpio_rd(data, eds_reg)?;
+
+        for shift in 0..32u32 {
+            let mask: u32 = bit(shift).into();
+            if pending & mask == 0 {
+                continue;
+            }
+
+            let hwirq = (bank as u32) * 32 + shift;
+            // Ack before dispatch (matches upstream pinctrl-bcm2835): clearing
+            // GPEDS first means an edge that arrives while the handler below
+            // is still running gets its own event-detect bit set again
+            // instead of being silently dropped by a post-handler clear.
+            Self::bcm2835_gpio_wr(data, eds_reg, mask)?;
+            irq::generic_handle(data.registrations().ok_or(ENXIO)?, hwirq)?;
+        }
+
+        Ok(())
+    }
 }
 
-//TODO: implement the items in trait gpio::Chip
 #[vtable]
 impl gpio::Chip for BCM2835Device {
     type Data = Arc<DeviceData>;
 
     fn get_direction(data: ArcBorrow<'_, DeviceData>, offset: u32) -> Result<gpio::LineDirection> {
-        let fsel = Self::bcm2835_pinctrl_fsel_get(data, offset as usize)?;
-
-        //Alternative function doesn't clearly provide a direction
-        if fsel > BCM2835_FSEL_GPIO_OUT {
-            //FIXME: Err(EINVAL)
-            return Err(ENOTSUPP);
+        // gpio::LineDirection has no alt-function variant, so this can't
+        // report an alt-muxed pin distinctly; that's surfaced through
+        // pinctrl::Mux::get_mux() instead, which is what the pinmux core
+        // actually consults for function state. Mirroring the upstream C
+        // driver, anything that isn't GPIO_IN is reported as Out here
+        // rather than erroring.
+        match Self::bcm2835_pinctrl_get_function(data, offset as usize)? {
+            PinFunction::Input => Ok(gpio::LineDirection::In),
+            PinFunction::Output | PinFunction::Alt(_) => Ok(gpio::LineDirection::Out),
         }
-
-        Ok(if fsel == BCM2835_FSEL_GPIO_IN {
-            gpio::LineDirection::In
-        } else {
-            gpio::LineDirection::Out
-        })
     }
 
     fn direction_input(data: ArcBorrow<'_, DeviceData>, offset: u32) -> Result {
@@ -172,18 +424,157 @@ impl gpio::Chip for BCM2835Device {
     fn get(data: ArcBorrow<'_, DeviceData>, offset: u32) -> Result<bool> {
         Self::bcm2835_gpio_get_bit(data, GPLEV0, offset)
     }
+
+    fn request(data: ArcBorrow<'_, DeviceData>, offset: u32, label: &CStr) -> Result {
+        // Allocate before taking the spinlock: CString::try_from() may sleep,
+        // and sleeping while holding a spinlock is not allowed.
+        let owned_label = CString::try_from(label)?;
+
+        let mut labels = data.labels.lock();
+        let slot = labels.get_mut(offset as usize).ok_or(EINVAL)?;
+
+        if slot.is_some() {
+            return Err(EBUSY);
+        }
+
+        *slot = Some(owned_label);
+        Ok(())
+    }
+
+    fn free(data: ArcBorrow<'_, DeviceData>, offset: u32) -> Result {
+        let mut labels = data.labels.lock();
+        let slot = labels.get_mut(offset as usize).ok_or(EINVAL)?;
+
+        if slot.take().is_none() {
+            return Err(EPERM);
+        }
+
+        Ok(())
+    }
+
+    // debugfs dump: one line per pin with its FSEL decoding, live level,
+    // pull bias and (if claimed) the requester's label — the same
+    // at-a-glance view upstream pinctrl-bcm2835 exposes.
+    fn dbg_show(data: ArcBorrow<'_, DeviceData>, seq: &mut SeqFile) {
+        for pin in 0..data.num_gpios as u32 {
+            let fsel_str = match Self::bcm2835_pinctrl_get_function(data, pin as usize) {
+                Ok(PinFunction::Input) => "in",
+                Ok(PinFunction::Output) => "out",
+                Ok(PinFunction::Alt(n)) => BCM2835_FSEL_ALT_NAMES.get(n).copied().unwrap_or("alt?"),
+                Err(_) => "unknown",
+            };
+
+            let level = Self::bcm2835_gpio_get_bit(data, GPLEV0, pin).unwrap_or(false);
+
+            let pull_str = match data.pull_state.lock().get(pin as usize).copied().flatten() {
+                Some(BCM2835_PUD_DOWN) => "pull down",
+                Some(BCM2835_PUD_UP) => "pull up",
+                Some(BCM2835_PUD_OFF) => "pull none",
+                _ => "pull unknown",
+            };
+
+            match data.labels.lock().get(pin as usize).and_then(|l| l.as_deref()) {
+                Some(label) => seq_print!(
+                    seq,
+                    "gpio-{:<3} ({}) {:<7} {:<4} {}\n",
+                    pin,
+                    label,
+                    fsel_str,
+                    if level { "hi" } else { "lo" },
+                    pull_str
+                ),
+                None => seq_print!(
+                    seq,
+                    "gpio-{:<3} (unused) {:<7} {:<4} {}\n",
+                    pin,
+                    fsel_str,
+                    if level { "hi" } else { "lo" },
+                    pull_str
+                ),
+            }
+        }
+    }
+}
+
+#[vtable]
+impl irq::Chip for BCM2835Device {
+    type Data = Arc<DeviceData>;
+
+    fn irq_mask(data: ArcBorrow<'_, DeviceData>, hwirq: u32) {
+        let _ = Self::bcm2835_irq_mask(data, hwirq);
+    }
+
+    fn irq_unmask(data: ArcBorrow<'_, DeviceData>, hwirq: u32) {
+        let _ = Self::bcm2835_irq_unmask(data, hwirq);
+    }
+
+    fn irq_set_type(data: ArcBorrow<'_, DeviceData>, hwirq: u32, irq_type: u32) -> Result {
+        Self::bcm2835_irq_set_type(data, hwirq, irq_type)
+    }
+}
+
+#[vtable]
+impl pinctrl::Config for BCM2835Device {
+    type Data = Arc<DeviceData>;
+
+    fn pin_config_set(data: ArcBorrow<'_, DeviceData>, pin: u32, param: u32, _arg: u32) -> Result {
+        let pull = match param {
+            PIN_CONFIG_BIAS_DISABLE => BCM2835_PUD_OFF,
+            PIN_CONFIG_BIAS_PULL_DOWN => BCM2835_PUD_DOWN,
+            PIN_CONFIG_BIAS_PULL_UP => BCM2835_PUD_UP,
+            _ => return Err(ENOTSUPP),
+        };
+
+        match data.pull_variant {
+            PullVariant::Bcm2835 => Self::bcm2835_pull_set(data, pin as usize, pull)?,
+            PullVariant::Bcm2711 => Self::bcm2711_pull_set(data, pin as usize, pull)?,
+        }
+
+        if let Some(slot) = data.pull_state.lock().get_mut(pin as usize) {
+            *slot = Some(pull);
+        }
+
+        Ok(())
+    }
+}
+
+#[vtable]
+impl pinctrl::Mux for BCM2835Device {
+    type Data = Arc<DeviceData>;
+
+    fn set_mux(data: ArcBorrow<'_, DeviceData>, pin: u32, altfn: u32) -> Result {
+        Self::bcm2835_pinctrl_set_mux(data, pin as usize, altfn as usize)
+    }
+
+    // What the pinmux core itself queries (e.g. for debugfs's
+    // pinmux-functions listing and gpio_request_enable()), so this is
+    // where alt-muxed pins get reported distinctly, not through the gpio
+    // facade's get_direction() which has no concept of them.
+    fn get_mux(data: ArcBorrow<'_, DeviceData>, pin: u32) -> Result<Option<u32>> {
+        match Self::bcm2835_pinctrl_get_function(data, pin as usize)? {
+            PinFunction::Alt(altfn) => Ok(Some(altfn as u32)),
+            PinFunction::Input | PinFunction::Output => Ok(None),
+        }
+    }
 }
 
 impl platform::Driver for BCM2835Device {
     type Data = Arc<DeviceData>;
 
-    define_of_id_table! {(),[
-        //FIXME: None is likely not correct, should fix it maybe
-        (of::DeviceId::Compatible(b"brcm,bcm2835-gpio"),None),
+    define_of_id_table! {BCM2835IdInfo,[
+        (of::DeviceId::Compatible(b"brcm,bcm2835-gpio"), Some(BCM2835IdInfo {
+            num_gpios: BCM2835_NUM_GPIOS,
+            pull_variant: PullVariant::Bcm2835,
+        })),
+        (of::DeviceId::Compatible(b"brcm,bcm2711-gpio"), Some(BCM2835IdInfo {
+            num_gpios: BCM2711_NUM_GPIOS,
+            pull_variant: PullVariant::Bcm2711,
+        })),
     ]}
 
-    fn probe(dev: &mut platform::Device, _data: Option<&Self::IdInfo>) -> Result<Arc<DeviceData>> {
+    fn probe(dev: &mut platform::Device, id_info: Option<&Self::IdInfo>) -> Result<Arc<DeviceData>> {
         let res = dev.res().ok_or(ENXIO)?;
+        let info = id_info.copied().ok_or(ENXIO)?;
 
         let data = kernel::new_device_data!(
             gpio::Registration::new(),
@@ -193,6 +584,11 @@ impl platform::Driver for BCM2835Device {
             },
             BCM2835Data {
                 dev: device::Device::from_dev(dev),
+                num_gpios: info.num_gpios,
+                pull_variant: info.pull_variant,
+                irq_lock: SpinLock::new([0; MAX_GPIOS]),
+                labels: SpinLock::new([(); MAX_GPIOS].map(|_| None)),
+                pull_state: SpinLock::new([None; MAX_GPIOS]),
             },
             "BCM2835::Regsiterations"
         )?;
@@ -201,12 +597,49 @@ impl platform::Driver for BCM2835Device {
 
         kernel::gpio_chip_register!(
             data.registrations().ok_or(ENXIO)?.as_pinned_mut(),
-            BCM2835_NUM_GPIOS,
+            info.num_gpios,
             None,
             dev,
             data.clone()
         )?;
 
+        // The BCM2835 wires three parent IRQ lines into the GIC: bank 0,
+        // bank 1, and an "all banks" catch-all used by some boards.
+        let irq_bank0 = dev.irq_by_index(0)?;
+        let irq_bank1 = dev.irq_by_index(1)?;
+        let irq_all = dev.irq_by_index(2)?;
+
+        kernel::gpio_irqchip_register!(
+            data.registrations().ok_or(ENXIO)?.as_pinned_mut(),
+            [irq_bank0, irq_bank1, irq_all],
+            // gpio_irqchip_register! calls back with the index into the
+            // parent-IRQ array above (0, 1 or 2), not the parent's raw
+            // hardware IRQ number, so bank routing can be a plain match on it.
+            |data: ArcBorrow<'_, DeviceData>, parent_idx: usize| {
+                match parent_idx {
+                    0 => Self::bcm2835_gpio_irq_bank_handler(data, 0),
+                    1 => Self::bcm2835_gpio_irq_bank_handler(data, 1),
+                    _ => {
+                        for bank in 0..BCM2835_NUM_BANKS {
+                            Self::bcm2835_gpio_irq_bank_handler(data, bank)?;
+                        }
+                        Ok(())
+                    }
+                }
+            },
+            data.clone()
+        )?;
+
+        kernel::pinctrl_config_register!(
+            data.registrations().ok_or(ENXIO)?.as_pinned_mut(),
+            data.clone()
+        )?;
+
+        kernel::pinctrl_mux_register!(
+            data.registrations().ok_or(ENXIO)?.as_pinned_mut(),
+            data.clone()
+        )?;
+
         dev_info!(data.dev, "RUST BCM2835 GPIO CHIP registered!!!\n");
 
         Ok(data)